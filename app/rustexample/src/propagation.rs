@@ -0,0 +1,137 @@
+use opentelemetry::{global, propagation::Extractor, propagation::Injector};
+use rocket::request::{self, FromRequest, Request};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Adapts Rocket's header map to the `opentelemetry::propagation::Extractor`
+/// trait so the global propagator can pull a parent context out of an
+/// incoming request.
+///
+/// `HeaderMap::iter()` yields owned `Header` values per iteration, so header
+/// names are collected into owned `String`s up front; `keys()` then borrows
+/// from that, not from the transient iterator item.
+pub struct RocketHeaderExtractor<'a> {
+    headers: &'a rocket::http::HeaderMap<'a>,
+    names: Vec<String>,
+}
+
+impl<'a> RocketHeaderExtractor<'a> {
+    pub fn new(headers: &'a rocket::http::HeaderMap<'a>) -> Self {
+        let names = headers
+            .iter()
+            .map(|header| header.name().as_str().to_string())
+            .collect();
+        RocketHeaderExtractor { headers, names }
+    }
+}
+
+impl<'a> Extractor for RocketHeaderExtractor<'a> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.headers.get_one(key)
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.names.iter().map(String::as_str).collect()
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RocketHeaderExtractor<'r> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(RocketHeaderExtractor::new(req.headers()))
+    }
+}
+
+/// Adapts a `reqwest::Request`'s header map to the
+/// `opentelemetry::propagation::Injector` trait so the global propagator can
+/// write the current trace context onto an outbound request.
+struct RequestCarrier<'a>(&'a mut reqwest::header::HeaderMap);
+
+impl<'a> Injector for RequestCarrier<'a> {
+    fn set(&mut self, key: &str, value: String) {
+        if let Ok(name) = reqwest::header::HeaderName::from_bytes(key.as_bytes()) {
+            if let Ok(value) = reqwest::header::HeaderValue::from_str(&value) {
+                self.0.insert(name, value);
+            }
+        }
+    }
+}
+
+/// Injects the current span's OpenTelemetry context into a downstream
+/// `reqwest::Request`'s headers, so traces continue across this call.
+pub fn inject_current_context(mut req: reqwest::Request) -> reqwest::Request {
+    let cx = tracing::Span::current().context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&cx, &mut RequestCarrier(req.headers_mut()));
+    });
+    req
+}
+
+/// Extracts the parent OpenTelemetry context from incoming request headers
+/// and attaches it to the current `tracing::Span` via `set_parent`.
+pub fn attach_parent_context(headers: &rocket::http::HeaderMap<'_>) {
+    let extractor = RocketHeaderExtractor::new(headers);
+    let parent_cx = global::get_text_map_propagator(|prop| prop.extract(&extractor));
+    tracing::Span::current().set_parent(parent_cx);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{TraceContextExt, TraceId};
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+    const TRACE_ID_HEX: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+    fn with_otel_test_subscriber<T>(f: impl FnOnce() -> T) -> T {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let subscriber = tracing_subscriber::registry().with(tracing_opentelemetry::layer());
+        tracing::subscriber::with_default(subscriber, f)
+    }
+
+    fn headers_with_traceparent() -> rocket::http::HeaderMap<'static> {
+        let mut headers = rocket::http::HeaderMap::new();
+        headers.add_raw("traceparent", TRACEPARENT);
+        headers
+    }
+
+    #[test]
+    fn attach_parent_context_links_incoming_trace_id() {
+        with_otel_test_subscriber(|| {
+            let span = tracing::info_span!("test_request");
+            let _entered = span.enter();
+
+            attach_parent_context(&headers_with_traceparent());
+
+            let trace_id = span.context().span().span_context().trace_id();
+            assert_eq!(trace_id, TraceId::from_hex(TRACE_ID_HEX).unwrap());
+        });
+    }
+
+    #[test]
+    fn inject_current_context_propagates_inbound_trace_id() {
+        with_otel_test_subscriber(|| {
+            let span = tracing::info_span!("test_request");
+            let _entered = span.enter();
+
+            attach_parent_context(&headers_with_traceparent());
+
+            let req = reqwest::Request::new(
+                reqwest::Method::GET,
+                "http://downstream.internal/health".parse().unwrap(),
+            );
+            let req = inject_current_context(req);
+
+            let traceparent = req
+                .headers()
+                .get("traceparent")
+                .expect("traceparent header should be injected")
+                .to_str()
+                .unwrap();
+            assert!(traceparent.contains(TRACE_ID_HEX));
+        });
+    }
+}
@@ -0,0 +1,326 @@
+//! Reusable telemetry bootstrap: wires up structured JSON logging and an
+//! OTLP tracer with sensible resource attributes. Other binaries in the
+//! workspace can pull this module in and just call `telemetry::init(name)`.
+
+use opentelemetry::{
+    global,
+    propagation::{TextMapCompositePropagator, TextMapPropagator},
+    trace::TracerProvider as _,
+    KeyValue,
+};
+use opentelemetry_jaeger_propagator::Propagator as JaegerPropagator;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    propagation::{BaggagePropagator, TraceContextPropagator},
+    runtime::Tokio,
+    trace::{BatchSpanProcessor, SimpleSpanProcessor, Tracer, TracerProvider},
+    Resource,
+};
+use opentelemetry_zipkin::{B3Encoding, Propagator as B3Propagator};
+use tracing_appender::{non_blocking::WorkerGuard, rolling::Rotation};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+/// Context propagation format, chosen via `OTEL_PROPAGATORS`.
+enum Propagator {
+    W3C,
+    B3,
+    B3Multi,
+    Jaeger,
+}
+
+impl Propagator {
+    fn from_env() -> Self {
+        match std::env::var("OTEL_PROPAGATORS").as_deref() {
+            Ok("b3") => Propagator::B3,
+            Ok("b3multi") => Propagator::B3Multi,
+            Ok("jaeger") => Propagator::Jaeger,
+            _ => Propagator::W3C,
+        }
+    }
+
+    /// Installs the selected propagator as the global text map propagator,
+    /// composed with a baggage propagator so the W3C `baggage` header still
+    /// round-trips regardless of the trace format in use.
+    fn install(self) {
+        let trace_propagator: Box<dyn TextMapPropagator + Send + Sync> = match self {
+            Propagator::W3C => Box::new(TraceContextPropagator::new()),
+            Propagator::B3 => Box::new(B3Propagator::with_encoding(B3Encoding::SingleHeader)),
+            Propagator::B3Multi => {
+                Box::new(B3Propagator::with_encoding(B3Encoding::MultipleHeader))
+            }
+            Propagator::Jaeger => Box::new(JaegerPropagator::new()),
+        };
+
+        global::set_text_map_propagator(TextMapCompositePropagator::new(vec![
+            trace_propagator,
+            Box::new(BaggagePropagator::new()),
+        ]));
+    }
+}
+
+/// Wire protocol used to talk to the OTLP collector.
+enum ExporterProtocol {
+    Grpc,
+    HttpProtobuf,
+}
+
+/// How finished spans are handed off to the exporter.
+enum SpanProcessorKind {
+    /// Buffer spans and export them in batches on a background task.
+    Batch,
+    /// Export each span synchronously as it ends; useful for short-lived
+    /// tools where a batch interval might never fire.
+    Simple,
+}
+
+/// Configuration for the OTLP exporter, sourced from the
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` / `OTEL_EXPORTER_OTLP_PROTOCOL` env vars,
+/// with the span processor chosen by `OTEL_SPAN_PROCESSOR`.
+struct TracerConfig {
+    endpoint: String,
+    protocol: ExporterProtocol,
+    processor: SpanProcessorKind,
+}
+
+impl TracerConfig {
+    fn from_env() -> Self {
+        let endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+            .unwrap_or_else(|_| "http://tempo:55690".to_string());
+        let protocol = match std::env::var("OTEL_EXPORTER_OTLP_PROTOCOL").as_deref() {
+            Ok("http/protobuf") => ExporterProtocol::HttpProtobuf,
+            _ => ExporterProtocol::Grpc,
+        };
+        let processor = match std::env::var("OTEL_SPAN_PROCESSOR").as_deref() {
+            Ok("simple") => SpanProcessorKind::Simple,
+            _ => SpanProcessorKind::Batch,
+        };
+
+        TracerConfig {
+            endpoint,
+            protocol,
+            processor,
+        }
+    }
+}
+
+/// Rolling file log sink, enabled by setting `LOG_DIR`. Rotation defaults to
+/// daily and can be narrowed with `LOG_ROTATION` (`daily`, `hourly`,
+/// `minutely`, `never`).
+struct FileLogConfig {
+    directory: String,
+    rotation: Rotation,
+}
+
+impl FileLogConfig {
+    fn from_env() -> Option<Self> {
+        let directory = std::env::var("LOG_DIR").ok()?;
+        let rotation = match std::env::var("LOG_ROTATION").as_deref() {
+            Ok("hourly") => Rotation::HOURLY,
+            Ok("minutely") => Rotation::MINUTELY,
+            Ok("never") => Rotation::NEVER,
+            _ => Rotation::DAILY,
+        };
+        Some(FileLogConfig { directory, rotation })
+    }
+}
+
+/// Returned by [`init`]; keeps the tracer provider alive and flushes
+/// buffered spans and file logs on drop.
+pub struct TelemetryGuard {
+    tracer_provider: TracerProvider,
+    _file_log_guard: Option<WorkerGuard>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Err(err) = self.tracer_provider.shutdown() {
+            eprintln!("failed to shut down tracer provider: {err}");
+        }
+    }
+}
+
+/// Builds the OTel `Resource` with semantic-convention attributes that
+/// backends key dashboards on: `service.name`, `service.version`,
+/// `host.name`, and a per-process `service.instance.id`.
+fn build_resource(service_name: &str) -> Resource {
+    let host_name = gethostname::gethostname().to_string_lossy().into_owned();
+    let instance_id = uuid::Uuid::new_v4().to_string();
+
+    Resource::new(vec![
+        KeyValue::new("service.name", service_name.to_string()),
+        KeyValue::new("host.name", host_name),
+        KeyValue::new("service.instance.id", instance_id),
+        KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+    ])
+}
+
+fn init_tracer(service_name: &str) -> Result<(TracerProvider, Tracer), Box<dyn std::error::Error>> {
+    let config = TracerConfig::from_env();
+
+    let exporter = match config.protocol {
+        ExporterProtocol::Grpc => opentelemetry_otlp::SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(&config.endpoint)
+            .build()?,
+        ExporterProtocol::HttpProtobuf => opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .with_endpoint(&config.endpoint)
+            .build()?,
+    };
+
+    let provider_builder = TracerProvider::builder();
+    let provider_builder = match config.processor {
+        SpanProcessorKind::Batch => provider_builder
+            .with_span_processor(BatchSpanProcessor::builder(exporter, Tokio).build()),
+        SpanProcessorKind::Simple => {
+            provider_builder.with_span_processor(SimpleSpanProcessor::new(Box::new(exporter)))
+        }
+    };
+    let provider = provider_builder
+        .with_resource(build_resource(service_name))
+        .build();
+
+    global::set_tracer_provider(provider.clone());
+    let tracer = provider.tracer(service_name.to_string());
+    Propagator::from_env().install();
+    Ok((provider, tracer))
+}
+
+fn init_logging(
+    tracer: Tracer,
+    service_name: &str,
+) -> Result<Option<WorkerGuard>, Box<dyn std::error::Error>> {
+    let (file_layer, file_guard) = match FileLogConfig::from_env() {
+        Some(config) => {
+            let appender = tracing_appender::rolling::RollingFileAppender::new(
+                config.rotation,
+                config.directory,
+                format!("{service_name}.log"),
+            );
+            let (non_blocking, guard) = tracing_appender::non_blocking(appender);
+            let layer = tracing_subscriber::fmt::layer()
+                .json()
+                .with_target(true)
+                .with_writer(non_blocking);
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_current_span(true)
+                .with_span_list(true)
+                .with_target(true)
+                .with_thread_ids(true)
+                .with_thread_names(true),
+        )
+        .with(file_layer)
+        .with(
+            tracing_subscriber::EnvFilter::from_default_env()
+                .add_directive(tracing::Level::INFO.into()),
+        )
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .init();
+
+    Ok(file_guard)
+}
+
+/// Initializes JSON stdout logging (plus an optional rolling file sink, see
+/// `LOG_DIR`) and the OTLP tracer for `service_name`. Hold on to the
+/// returned guard for the lifetime of the process; dropping it (e.g. at the
+/// end of `main`) flushes any buffered spans and file logs.
+pub fn init(service_name: &str) -> Result<TelemetryGuard, Box<dyn std::error::Error>> {
+    let (tracer_provider, tracer) = init_tracer(service_name)?;
+    let file_log_guard = init_logging(tracer, service_name)?;
+    Ok(TelemetryGuard {
+        tracer_provider,
+        _file_log_guard: file_log_guard,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // `from_env` reads process-wide env vars, so tests that touch them are
+    // serialized to avoid racing each other under the default test runner.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn tracer_config_defaults_to_grpc_batch() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        std::env::remove_var("OTEL_EXPORTER_OTLP_ENDPOINT");
+        std::env::remove_var("OTEL_EXPORTER_OTLP_PROTOCOL");
+        std::env::remove_var("OTEL_SPAN_PROCESSOR");
+
+        let config = TracerConfig::from_env();
+
+        assert_eq!(config.endpoint, "http://tempo:55690");
+        assert!(matches!(config.protocol, ExporterProtocol::Grpc));
+        assert!(matches!(config.processor, SpanProcessorKind::Batch));
+    }
+
+    #[test]
+    fn tracer_config_reads_env_overrides() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        std::env::set_var("OTEL_EXPORTER_OTLP_ENDPOINT", "http://collector:4318");
+        std::env::set_var("OTEL_EXPORTER_OTLP_PROTOCOL", "http/protobuf");
+        std::env::set_var("OTEL_SPAN_PROCESSOR", "simple");
+
+        let config = TracerConfig::from_env();
+
+        std::env::remove_var("OTEL_EXPORTER_OTLP_ENDPOINT");
+        std::env::remove_var("OTEL_EXPORTER_OTLP_PROTOCOL");
+        std::env::remove_var("OTEL_SPAN_PROCESSOR");
+
+        assert_eq!(config.endpoint, "http://collector:4318");
+        assert!(matches!(config.protocol, ExporterProtocol::HttpProtobuf));
+        assert!(matches!(config.processor, SpanProcessorKind::Simple));
+    }
+
+    #[test]
+    fn propagator_defaults_to_w3c() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        std::env::remove_var("OTEL_PROPAGATORS");
+
+        assert!(matches!(Propagator::from_env(), Propagator::W3C));
+    }
+
+    #[test]
+    fn propagator_reads_b3multi() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        std::env::set_var("OTEL_PROPAGATORS", "b3multi");
+
+        let propagator = Propagator::from_env();
+        std::env::remove_var("OTEL_PROPAGATORS");
+
+        assert!(matches!(propagator, Propagator::B3Multi));
+    }
+
+    #[test]
+    fn file_log_config_disabled_without_log_dir() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        std::env::remove_var("LOG_DIR");
+
+        assert!(FileLogConfig::from_env().is_none());
+    }
+
+    #[test]
+    fn file_log_config_reads_rotation() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        std::env::set_var("LOG_DIR", "/tmp/rustexample-logs");
+        std::env::set_var("LOG_ROTATION", "hourly");
+
+        let config = FileLogConfig::from_env().expect("LOG_DIR is set");
+
+        std::env::remove_var("LOG_DIR");
+        std::env::remove_var("LOG_ROTATION");
+
+        assert_eq!(config.directory, "/tmp/rustexample-logs");
+        assert_eq!(config.rotation, Rotation::HOURLY);
+    }
+}
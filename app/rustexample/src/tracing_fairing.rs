@@ -0,0 +1,97 @@
+use std::sync::Mutex;
+
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::request::{self, FromRequest};
+use rocket::{Data, Request, Response};
+use tracing::Span;
+
+use crate::propagation;
+
+/// Per-request slot holding the span opened in `on_request`. Plain `Span`
+/// (not a guard) because it has to be `Send + Sync` to live in
+/// `Request::local_cache`, and it may be read back on a different thread
+/// than the one that created it.
+#[derive(Default)]
+struct SpanSlot(Mutex<Option<Span>>);
+
+/// Rocket fairing that opens a `tracing` span for every incoming request and
+/// links it to the inbound trace context.
+pub struct TracingFairing;
+
+impl TracingFairing {
+    pub fn new() -> Self {
+        TracingFairing
+    }
+}
+
+impl Default for TracingFairing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for TracingFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "OpenTelemetry request tracing",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _data: &mut Data<'_>) {
+        let route = req
+            .route()
+            .map(|route| route.uri.to_string())
+            .unwrap_or_else(|| req.uri().path().to_string());
+        let client_ip = req
+            .client_ip()
+            .map(|ip| ip.to_string())
+            .unwrap_or_default();
+
+        let span = tracing::info_span!(
+            "http_request",
+            "otel.name" = %format!("{} {}", req.method(), route),
+            "otel.kind" = "server",
+            "http.method" = %req.method(),
+            "http.route" = %route,
+            "http.target" = %req.uri().path(),
+            "http.client_ip" = %client_ip,
+            "http.status_code" = tracing::field::Empty,
+        );
+
+        // Only entered for this synchronous call, never held across an
+        // await: `in_scope` links the span to the inbound trace context and
+        // exits before this function returns.
+        span.in_scope(|| propagation::attach_parent_context(req.headers()));
+
+        *req.local_cache(SpanSlot::default).0.lock().unwrap() = Some(span);
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if let Some(span) = req.local_cache(SpanSlot::default).0.lock().unwrap().take() {
+            span.record("http.status_code", res.status().code as i64);
+        }
+    }
+}
+
+/// Request guard exposing the span `TracingFairing` opened for this
+/// request. Wrap handler bodies with
+/// `tracing::Instrument::instrument(req_span.0)` to run inside it.
+pub struct RequestSpan(pub Span);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RequestSpan {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let span = req
+            .local_cache(SpanSlot::default)
+            .0
+            .lock()
+            .unwrap()
+            .clone()
+            .unwrap_or_else(Span::none);
+        request::Outcome::Success(RequestSpan(span))
+    }
+}